@@ -7,20 +7,24 @@ struct Args {
     #[arg(short, long, default_value_t = ShellType::Bash)]
     shell_type: ShellType,
     #[arg(short, long)]
-    /// Path to custom history shell file;
-    /// expects that the file is formatted just like other shell history files.
+    /// Path to custom history shell file, or "-" to read history piped in
+    /// over stdin; expects that the file is formatted just like other shell
+    /// history files.
     path_to_history: Option<String>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    let mut shell = Shell::from(args.shell_type);
+    let mut shell = match args.path_to_history {
+        Some(path) => Shell::from_custom(&path)?,
+        None => Shell::try_from(args.shell_type)?,
+    };
     let freq = shell.command_frequency()?;
 
     println!("{freq:?}");
 
-    println!("{}", shell.command_count.unwrap());
+    println!("{}", shell.invocation_count.unwrap());
 
     Ok(())
 }
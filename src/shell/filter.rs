@@ -0,0 +1,31 @@
+use regex::RegexSet;
+
+use super::{Result, Shell, ShellError};
+
+/// A reasonable baseline ignore list: commands that rarely say anything
+/// interesting about how someone uses their shell, plus the
+/// `HISTCONTROL=ignorespace`/`setopt HIST_IGNORE_SPACE` convention of a
+/// leading space meaning "don't remember this one"
+pub const DEFAULT_IGNORES: &[&str] = &[r"^\s", r"^ls$", r"^cd$", r"^clear$", r"^exit$", r"^pwd$"];
+
+impl Shell {
+    /// Compiles `patterns` into a `RegexSet`; `command_frequency`,
+    /// `invocation_frequency`, and `top_commands_and_invocations` then skip
+    /// any history entry whose full invocation matches it
+    pub fn with_ignores(mut self, patterns: &[&str]) -> Result<Self> {
+        self.ignores =
+            Some(RegexSet::new(patterns).map_err(|_| ShellError::InvalidIgnorePattern)?);
+
+        Ok(self)
+    }
+
+    /// Opts into `DEFAULT_IGNORES`
+    pub fn with_default_ignores(self) -> Result<Self> {
+        self.with_ignores(DEFAULT_IGNORES)
+    }
+
+    /// Whether a full invocation matches the configured ignore set, if any
+    pub(super) fn is_ignored(&self, line: &str) -> bool {
+        self.ignores.as_ref().is_some_and(|set| set.is_match(line))
+    }
+}
@@ -0,0 +1,179 @@
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use super::{Entry, Importer};
+use crate::shell::{Result, ShellError};
+
+/// Reads fish's `fish_history` format: a YAML-like list of
+/// `- cmd: <command>` entries, each optionally followed by an indented
+/// `when: <epoch>` timestamp and a `paths:` block of files the command
+/// touched. Commands that span multiple lines are stored as further
+/// indented continuation lines under the same entry.
+pub struct FishImporter<R: Read + Seek> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read + Seek> FishImporter<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            reader: BufReader::new(source),
+        }
+    }
+}
+
+impl<R: Read + Seek> Importer for FishImporter<R> {
+    fn entries(&mut self) -> Result<Box<dyn Iterator<Item = Result<Entry>> + '_>> {
+        self.reader.rewind().map_err(|_| ShellError::ReadError)?;
+
+        let mut entries = Vec::new();
+        let mut current: Option<Entry> = None;
+        let mut in_paths = false;
+
+        for line in (&mut self.reader).lines() {
+            let line = line.map_err(|_| ShellError::ReadError)?;
+
+            if let Some(cmd) = line.strip_prefix("- cmd: ") {
+                if let Some(entry) = current.take() {
+                    entries.push(entry);
+                }
+                current = Some((unescape(cmd), None));
+                in_paths = false;
+                continue;
+            }
+
+            let trimmed = line.trim_start();
+
+            if let Some(when) = trimmed.strip_prefix("when: ") {
+                if let Some((_, timestamp)) = current.as_mut() {
+                    *timestamp = when.trim().parse().ok();
+                }
+                in_paths = false;
+                continue;
+            }
+
+            if trimmed.starts_with("paths:") {
+                in_paths = true;
+                continue;
+            }
+
+            if in_paths && trimmed.starts_with("- ") {
+                continue;
+            }
+
+            // anything else that's still indented is a continuation of the
+            // previous `cmd:`'s multi-line command
+            if !trimmed.is_empty() && line.starts_with(' ') {
+                if let Some((cmd, _)) = current.as_mut() {
+                    cmd.push('\n');
+                    cmd.push_str(&unescape(trimmed));
+                }
+            }
+        }
+
+        if let Some(entry) = current.take() {
+            entries.push(entry);
+        }
+
+        Ok(Box::new(entries.into_iter().map(Ok)))
+    }
+}
+
+/// Undoes the minimal escaping fish applies to block scalars, consuming
+/// `\\` and `\n` as whole two-character escapes in a single left-to-right
+/// pass so an escaped backslash followed by a literal `n` (`\\n`, i.e. `\`
+/// then `n`) isn't mistaken for an escaped newline (`\n`)
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn parses_cmd_and_when_and_skips_paths() {
+        let source = Cursor::new(
+            concat!(
+                "- cmd: git status\n",
+                "  when: 1609459200\n",
+                "- cmd: rm old.txt\n",
+                "  when: 1609459300\n",
+                "  paths:\n",
+                "    - old.txt\n",
+            )
+            .as_bytes()
+            .to_vec(),
+        );
+        let mut importer = FishImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("git status".to_string(), Some(1609459200)),
+                ("rm old.txt".to_string(), Some(1609459300)),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_mistake_an_escaped_backslash_and_a_literal_n_for_an_escaped_newline() {
+        let source = Cursor::new(b"- cmd: printf 'a\\\\nb'\n".to_vec());
+        let mut importer = FishImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(entries, vec![("printf 'a\\nb'".to_string(), None)]);
+    }
+
+    #[test]
+    fn joins_indented_continuation_lines_into_the_command() {
+        let source = Cursor::new(
+            concat!("- cmd: printf foo\n", "    bar\n", "  when: 1609459200\n",)
+                .as_bytes()
+                .to_vec(),
+        );
+        let mut importer = FishImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![("printf foo\nbar".to_string(), Some(1609459200))]
+        );
+    }
+}
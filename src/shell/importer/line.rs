@@ -0,0 +1,68 @@
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use super::{Entry, Importer};
+use crate::shell::{Result, ShellError};
+
+/// Reads history as plain newline-delimited lines: bash's format, and the
+/// fallback for any custom history file without a richer structure. Every
+/// entry comes back with no timestamp.
+pub struct LineImporter<R: Read + Seek> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read + Seek> LineImporter<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            reader: BufReader::new(source),
+        }
+    }
+}
+
+impl<R: Read + Seek> Importer for LineImporter<R> {
+    fn entries(&mut self) -> Result<Box<dyn Iterator<Item = Result<Entry>> + '_>> {
+        self.reader.rewind().map_err(|_| ShellError::ReadError)?;
+
+        Ok(Box::new((&mut self.reader).lines().map(|line| {
+            line.map(|line| (line, None)).map_err(|_| ShellError::ReadError)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn yields_each_line_with_no_timestamp() {
+        let source = Cursor::new(b"ls\ncd ~\ngit status\n".to_vec());
+        let mut importer = LineImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("ls".to_string(), None),
+                ("cd ~".to_string(), None),
+                ("git status".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_can_be_read_more_than_once() {
+        let source = Cursor::new(b"ls\n".to_vec());
+        let mut importer = LineImporter::new(source);
+
+        let first = importer.entries().unwrap().count();
+        let second = importer.entries().unwrap().count();
+
+        assert_eq!(first, second);
+    }
+}
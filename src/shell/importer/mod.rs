@@ -0,0 +1,28 @@
+use super::Result;
+
+mod fish;
+mod line;
+mod resh;
+mod zsh;
+
+pub use fish::FishImporter;
+pub use line::LineImporter;
+pub use resh::ReshImporter;
+pub use zsh::ZshImporter;
+
+/// A single normalized history entry: the full command line, and, for
+/// formats that record one, the unix timestamp it was run at
+pub type Entry = (String, Option<i64>);
+
+/// A source of normalized history entries, decoupled from both where the
+/// bytes come from and how the underlying shell formats them.
+///
+/// Implementors wrap a `BufReader` over any `Read + Seek` source (a file, an
+/// in-memory `Cursor`, ...) so the rest of `Shell` never has to know whether
+/// it's reading raw bash lines, fish's YAML-like history, or resh's
+/// JSON-lines, nor whether the source is `~/.bash_history` or a fake history
+/// fed in from a test.
+pub trait Importer {
+    /// Returns an iterator over each history entry in the underlying source
+    fn entries(&mut self) -> Result<Box<dyn Iterator<Item = Result<Entry>> + '_>>;
+}
@@ -0,0 +1,103 @@
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use serde::Deserialize;
+
+use super::{Entry, Importer};
+use crate::shell::{Result, ShellError};
+
+/// One line of resh's `.resh_history.json` JSON-lines log
+#[derive(Deserialize)]
+struct ReshRecord {
+    #[serde(rename = "cmdLine")]
+    cmd_line: String,
+    #[serde(rename = "realtimeBefore")]
+    realtime_before: Option<f64>,
+}
+
+/// Reads resh's history format: one JSON object per line, each recording
+/// the command line alongside a realtime-before timestamp
+pub struct ReshImporter<R: Read + Seek> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read + Seek> ReshImporter<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            reader: BufReader::new(source),
+        }
+    }
+}
+
+impl<R: Read + Seek> Importer for ReshImporter<R> {
+    fn entries(&mut self) -> Result<Box<dyn Iterator<Item = Result<Entry>> + '_>> {
+        self.reader.rewind().map_err(|_| ShellError::ReadError)?;
+
+        Ok(Box::new((&mut self.reader).lines().filter_map(|line| {
+            let line = match line.map_err(|_| ShellError::ReadError) {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if line.trim().is_empty() {
+                return None;
+            }
+
+            let record: ReshRecord = match serde_json::from_str(&line) {
+                Ok(record) => record,
+                Err(_) => return Some(Err(ShellError::ParseError(line))),
+            };
+
+            let timestamp = record.realtime_before.map(|secs| secs as i64);
+
+            Some(Ok((record.cmd_line, timestamp)))
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn parses_json_lines_and_skips_blank_lines() {
+        let source = Cursor::new(
+            concat!(
+                "{\"cmdLine\": \"git status\", \"realtimeBefore\": 1609459200.5}\n",
+                "\n",
+                "{\"cmdLine\": \"ls\"}\n",
+            )
+            .as_bytes()
+            .to_vec(),
+        );
+        let mut importer = ReshImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("git status".to_string(), Some(1609459200)),
+                ("ls".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_json_yields_a_parse_error() {
+        let source = Cursor::new(b"not json\n".to_vec());
+        let mut importer = ReshImporter::new(source);
+
+        let result = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>();
+
+        assert!(matches!(result, Err(ShellError::ParseError(_))));
+    }
+}
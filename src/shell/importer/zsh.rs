@@ -0,0 +1,83 @@
+use std::io::{BufRead, BufReader, Read, Seek};
+
+use super::{Entry, Importer};
+use crate::shell::{Result, ShellError};
+
+/// Reads zsh's `EXTENDED_HISTORY` format, where each entry is written as
+/// `: <start>:<elapsed>;<command>`. Lines matching that shape yield the
+/// command with its start timestamp; anything else is treated as a plain
+/// command with no timestamp, so plain (non-extended) zsh history still
+/// works.
+pub struct ZshImporter<R: Read + Seek> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read + Seek> ZshImporter<R> {
+    pub fn new(source: R) -> Self {
+        Self {
+            reader: BufReader::new(source),
+        }
+    }
+}
+
+impl<R: Read + Seek> Importer for ZshImporter<R> {
+    fn entries(&mut self) -> Result<Box<dyn Iterator<Item = Result<Entry>> + '_>> {
+        self.reader.rewind().map_err(|_| ShellError::ReadError)?;
+
+        Ok(Box::new((&mut self.reader).lines().map(|line| {
+            line.map(|line| parse_extended(&line))
+                .map_err(|_| ShellError::ReadError)
+        })))
+    }
+}
+
+/// Parses a single zsh history line, extracting the `: <start>:<elapsed>;`
+/// prefix if present
+fn parse_extended(line: &str) -> Entry {
+    let parsed = line
+        .strip_prefix(": ")
+        .and_then(|rest| rest.split_once(';'))
+        .and_then(|(meta, command)| {
+            let (start, elapsed) = meta.split_once(':')?;
+            let start = start.parse::<i64>().ok()?;
+            elapsed.parse::<i64>().ok()?;
+            Some((command.to_string(), Some(start)))
+        });
+
+    parsed.unwrap_or_else(|| (line.to_string(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn extracts_timestamp_and_command_from_extended_history() {
+        let source = Cursor::new(b": 1609459200:0;git push\n".to_vec());
+        let mut importer = ZshImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(entries, vec![("git push".to_string(), Some(1609459200))]);
+    }
+
+    #[test]
+    fn falls_back_to_the_whole_line_when_not_extended_history() {
+        let source = Cursor::new(b"git push\n".to_vec());
+        let mut importer = ZshImporter::new(source);
+
+        let entries = importer
+            .entries()
+            .unwrap()
+            .collect::<Result<Vec<Entry>>>()
+            .unwrap();
+
+        assert_eq!(entries, vec![("git push".to_string(), None)]);
+    }
+}
@@ -2,10 +2,24 @@ use clap::ValueEnum;
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{Cursor, Read};
 use std::path::PathBuf;
 use thiserror::Error;
 
+pub mod importer;
+
+pub use importer::{FishImporter, Importer, LineImporter, ReshImporter, ZshImporter};
+pub use stats::Activity;
+
+use importer::Entry;
+use regex::RegexSet;
+
+pub use filter::DEFAULT_IGNORES;
+
+mod filter;
+mod stats;
+mod tokenize;
+
 pub type Result<T, E = ShellError> = std::result::Result<T, E>;
 
 #[derive(Error, Debug)]
@@ -22,6 +36,10 @@ pub enum ShellError {
     ParseError(String),
     #[error("for some reason, the command count failed")]
     CountError,
+    #[error("history file contains no timestamped commands")]
+    NoActivity,
+    #[error("failed to compile ignore patterns")]
+    InvalidIgnorePattern,
 }
 
 #[derive(Clone, ValueEnum, Debug)]
@@ -30,6 +48,10 @@ pub enum ShellType {
     Zsh,
     /// the `.bash_history` file in the current user's home directory
     Bash,
+    /// the `fish_history` file in the current user's data directory
+    Fish,
+    /// the `.resh_history.json` file in the current user's home directory
+    Resh,
 }
 
 impl ShellType {
@@ -39,6 +61,8 @@ impl ShellType {
         Ok(match &self {
             ShellType::Zsh => home.join(".zsh_history"),
             ShellType::Bash => home.join(".bash_history"),
+            ShellType::Fish => home.join(".local/share/fish/fish_history"),
+            ShellType::Resh => home.join(".resh_history.json"),
         })
     }
 
@@ -62,94 +86,107 @@ impl Display for ShellType {
         let res = match &self {
             ShellType::Zsh => "zsh".to_string(),
             ShellType::Bash => "bash".to_string(),
+            ShellType::Fish => "fish".to_string(),
+            ShellType::Resh => "resh".to_string(),
         };
         write!(f, "{res}")
     }
 }
 
 pub struct Shell {
-    /// The type of shell to read the history from
-    shell_type: ShellType,
+    /// Where history entries are actually read from, and how they're parsed;
+    /// which `Importer` this is depends on the `ShellType` it was built from
+    importer: Box<dyn Importer>,
+    /// Invocations matching this set are skipped by `command_frequency`,
+    /// `invocation_frequency`, and `top_commands_and_invocations`;
+    /// set via `with_ignores`/`with_default_ignores`
+    ignores: Option<RegexSet>,
     /// The command count in the history file
     /// to find the command amount, use `command_frequency` or `commands_ran`
     pub invocation_count: Option<usize>,
 }
 
 impl Shell {
-    pub fn from_custom(path: &str) -> Self {
-        todo!()
-        // Self {
-        //     shell_type: Custom,
-        //     invocation_count: None,
-        // }
+    /// Treats an arbitrary file as `Bash`-style, newline-delimited history,
+    /// useful for hand-crafted history files. Pass `"-"` to read history
+    /// piped in over stdin instead of opening a file.
+    pub fn from_custom(path: &str) -> Result<Self> {
+        let importer: Box<dyn Importer> = if path == "-" {
+            let mut buf = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut buf)
+                .map_err(|_| ShellError::OpenError(path.to_string()))?;
+
+            Box::new(LineImporter::new(Cursor::new(buf)))
+        } else {
+            let file = File::open(path).map_err(|_| ShellError::OpenError(path.to_string()))?;
+
+            Box::new(LineImporter::new(file))
+        };
+
+        Ok(Self {
+            importer,
+            ignores: None,
+            invocation_count: None,
+        })
     }
 
     /// Sets the `invocation_count` field and returns it,
     /// or a `ShellError` on failure
     pub fn commands_ran(&mut self) -> Result<usize> {
-        let history_path = self.shell_type.find_history_path()?;
-
-        let file = File::open(&history_path).map_err(|_| {
-            let history_path = history_path.to_str();
-
-            if let Some(history_path) = history_path {
-                ShellError::OpenError(history_path.to_string())
-            } else {
-                ShellError::InvalidUTF8
-            }
-        })?;
-
-        let line_count = BufReader::new(file).lines().count();
-        self.invocation_count = Some(line_count);
+        let count = self.importer.entries()?.count();
+        self.invocation_count = Some(count);
 
-        self.invocation_count.ok_or(ShellError::CountError)
+        Ok(count)
     }
 
-    /// Returns a map of the frequency of each command
-    pub fn command_frequency(&self) -> Result<HashMap<String, usize>> {
-        let file = self.shell_type.open_history_file()?;
+    /// Collects raw history entries, joins backslash/here-doc continuations
+    /// into single logical entries, and drops any matching the configured
+    /// ignore set; shared by `command_frequency` and `invocation_frequency`
+    /// so a continued line counts as one invocation in both
+    fn logical_entries(&mut self) -> Result<Vec<Entry>> {
+        let entries = self
+            .importer
+            .entries()?
+            .collect::<Result<Vec<Entry>>>()?;
+
+        Ok(tokenize::join_continuations(entries)
+            .into_iter()
+            .filter(|(line, _)| !self.is_ignored(line))
+            .collect())
+    }
 
-        let buf = BufReader::new(file);
+    /// Returns a map of the frequency of each command and sets the
+    /// `invocation_count` field
+    pub fn command_frequency(&mut self) -> Result<HashMap<String, usize>> {
         let mut freq = HashMap::new();
-        buf.lines()
-            .collect::<std::io::Result<Vec<String>>>()
-            .map_err(|_| ShellError::ReadError)?
-            .into_iter()
-            .for_each(|line| {
-                // TODO: add support for | and && and \ commands
-                // get the first command that isn't a VAR
-                let Some(command) = line
-                    .split(' ')
-                    .filter(|arg| !arg.contains('=') && !arg.is_empty())
-                    .nth(0)
-                else {
-                    // continue interating in for_each
-                    return;
+
+        let entries = self.logical_entries()?;
+        self.invocation_count = Some(entries.len());
+
+        for (line, _) in entries {
+            for segment in tokenize::segments(&line) {
+                let Some(command) = tokenize::leading_binary(&segment) else {
+                    continue;
                 };
 
                 *freq.entry(command.to_string()).or_insert(0) += 1;
-            });
+            }
+        }
 
         Ok(freq)
     }
 
     /// Returns a map of the frequency of each invocation and sets the `invocation_count` field
     pub fn invocation_frequency(&mut self) -> Result<HashMap<String, usize>> {
-        let file = self.shell_type.open_history_file()?;
-
-        let buf = BufReader::new(file);
         let mut freq = HashMap::new();
-        let mut count = 0;
-        buf.lines()
-            .collect::<std::io::Result<Vec<String>>>()
-            .map_err(|_| ShellError::ReadError)?
-            .into_iter()
-            .for_each(|line| {
-                count += 1;
-                *freq.entry(line).or_insert(0) += 1;
-            });
 
-        self.invocation_count = Some(count);
+        let entries = self.logical_entries()?;
+        self.invocation_count = Some(entries.len());
+
+        for (line, _) in entries {
+            *freq.entry(line).or_insert(0) += 1;
+        }
 
         Ok(freq)
     }
@@ -190,11 +227,23 @@ impl Shell {
     }
 }
 
-impl From<ShellType> for Shell {
-    fn from(value: ShellType) -> Self {
-        Self {
-            shell_type: value,
+impl TryFrom<ShellType> for Shell {
+    type Error = ShellError;
+
+    fn try_from(value: ShellType) -> Result<Self> {
+        let file = value.open_history_file()?;
+
+        let importer: Box<dyn Importer> = match value {
+            ShellType::Bash => Box::new(LineImporter::new(file)),
+            ShellType::Zsh => Box::new(ZshImporter::new(file)),
+            ShellType::Fish => Box::new(FishImporter::new(file)),
+            ShellType::Resh => Box::new(ReshImporter::new(file)),
+        };
+
+        Ok(Self {
+            importer,
+            ignores: None,
             invocation_count: None,
-        }
+        })
     }
 }
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Local, Month, NaiveDate, TimeZone, Timelike, Weekday};
+
+use super::{Result, Shell, ShellError};
+
+/// A "year in review" summary of *when* commands were run, bucketed from
+/// whatever timestamps the history format recorded. Entries with no
+/// timestamp (a format that doesn't record one, or a line that failed to
+/// parse one) are ignored when building this.
+#[derive(Debug)]
+pub struct Activity {
+    /// The hour of the day (0-23, local time) with the most commands
+    pub busiest_hour: u32,
+    /// The weekday with the most commands
+    pub busiest_weekday: Weekday,
+    /// The month with the most commands
+    pub busiest_month: Month,
+    /// The date of the earliest timestamped command
+    pub first_command: NaiveDate,
+    /// The date of the most recent timestamped command
+    pub last_command: NaiveDate,
+    /// The longest run of consecutive days with at least one command
+    pub longest_streak: u32,
+}
+
+impl Shell {
+    /// Builds an `Activity` summary out of every timestamped entry in the
+    /// history file, or a `ShellError::NoActivity` if none carried one
+    pub fn activity(&mut self) -> Result<Activity> {
+        let mut hours = HashMap::new();
+        let mut weekdays = HashMap::new();
+        let mut months = HashMap::new();
+        let mut days = Vec::new();
+
+        for entry in self.importer.entries()? {
+            let (_, timestamp) = entry?;
+
+            let Some(timestamp) = timestamp else {
+                continue;
+            };
+            let Some(at) = Local.timestamp_opt(timestamp, 0).single() else {
+                continue;
+            };
+
+            *hours.entry(at.hour()).or_insert(0usize) += 1;
+            *weekdays.entry(at.weekday()).or_insert(0usize) += 1;
+            *months.entry(at.month()).or_insert(0usize) += 1;
+            days.push(at.date_naive());
+        }
+
+        days.sort_unstable();
+        days.dedup();
+
+        let first_command = *days.first().ok_or(ShellError::NoActivity)?;
+        let last_command = *days.last().ok_or(ShellError::NoActivity)?;
+
+        Ok(Activity {
+            busiest_hour: busiest(hours).unwrap_or_default(),
+            busiest_weekday: busiest(weekdays).unwrap_or(Weekday::Mon),
+            busiest_month: busiest(months)
+                .and_then(|month| Month::try_from(month as u8).ok())
+                .unwrap_or(Month::January),
+            first_command,
+            last_command,
+            longest_streak: longest_streak(&days),
+        })
+    }
+}
+
+/// Picks the key with the highest count out of a frequency map
+fn busiest<K>(freq: HashMap<K, usize>) -> Option<K> {
+    freq.into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(key, _)| key)
+}
+
+/// `days` must already be sorted and deduplicated
+fn longest_streak(days: &[NaiveDate]) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for &day in days {
+        current = match previous {
+            Some(previous) if day - previous == Duration::days(1) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(day);
+    }
+
+    longest
+}
@@ -0,0 +1,296 @@
+use super::importer::Entry;
+
+/// Joins backslash-continued lines and here-doc bodies into a single
+/// logical command, so a history entry split across several physical lines
+/// is tokenized as one.
+pub fn join_continuations(entries: Vec<Entry>) -> Vec<Entry> {
+    let mut joined = Vec::new();
+    let mut iter = entries.into_iter().peekable();
+
+    while let Some((mut line, timestamp)) = iter.next() {
+        while line.trim_end().ends_with('\\') {
+            let trimmed_len = line.trim_end().len();
+            line.truncate(trimmed_len - 1);
+            line.truncate(line.trim_end().len());
+
+            match iter.next() {
+                Some((next, _)) => {
+                    line.push(' ');
+                    line.push_str(&next);
+                }
+                None => break,
+            }
+        }
+
+        if let Some(delimiter) = heredoc_delimiter(&line) {
+            while let Some((next, _)) = iter.peek() {
+                if next.trim() == delimiter {
+                    iter.next();
+                    break;
+                }
+
+                let (next, _) = iter.next().expect("just peeked");
+                line.push('\n');
+                line.push_str(&next);
+            }
+        }
+
+        joined.push((line, timestamp));
+    }
+
+    joined
+}
+
+/// Finds the delimiter word of a `<<`/`<<-` here-doc, if this line opens one.
+/// The `<<` must be a real operator (not inside quotes or a `$(...)`/backtick
+/// substitution) and its delimiter must be the last token on the line, which
+/// rules out ordinary text or quoted strings that merely happen to contain a
+/// literal `<<` (e.g. `some text about <<here state machines>>`).
+fn heredoc_delimiter(line: &str) -> Option<String> {
+    let operator_at = find_heredoc_operator(line)?;
+
+    let rest = &line[operator_at + 2..];
+    let rest = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+    let rest = rest.strip_prefix(['\'', '"']).unwrap_or(rest);
+
+    let word: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+
+    if word.is_empty() {
+        return None;
+    }
+
+    let trailing = rest[word.len()..].trim_start_matches(['\'', '"']).trim();
+
+    trailing.is_empty().then_some(word)
+}
+
+/// Finds the byte offset of a `<<` here-doc operator that isn't inside
+/// single/double quotes or a `$(...)`/backtick command substitution, using
+/// the same quote-tracking rules as `segments`
+fn find_heredoc_operator(line: &str) -> Option<usize> {
+    let mut chars = line.char_indices().peekable();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '`' if !in_single && !in_double => {
+                for (_, nc) in chars.by_ref() {
+                    if nc == '`' {
+                        break;
+                    }
+                }
+            }
+            '$' if !in_single && chars.peek().map(|&(_, c)| c) == Some('(') => {
+                chars.next();
+                let mut depth = 1;
+                for (_, nc) in chars.by_ref() {
+                    match nc {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            '<' if !in_single && !in_double && chars.peek().map(|&(_, c)| c) == Some('<') => {
+                chars.next();
+                return Some(i);
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Splits a logical command on the shell operators `|`, `||`, `&&`, and
+/// `;`, without splitting inside single/double quotes or a `$(...)`/
+/// backtick command substitution.
+pub fn segments(command: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut chars = command.chars().peekable();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if !in_double => {
+                in_single = !in_single;
+                current.push(c);
+            }
+            '"' if !in_single => {
+                in_double = !in_double;
+                current.push(c);
+            }
+            '`' if !in_single && !in_double => {
+                current.push(c);
+                for nc in chars.by_ref() {
+                    current.push(nc);
+                    if nc == '`' {
+                        break;
+                    }
+                }
+            }
+            '$' if !in_single && chars.peek() == Some(&'(') => {
+                current.push(c);
+                current.push(chars.next().expect("peeked"));
+
+                let mut depth = 1;
+                for nc in chars.by_ref() {
+                    current.push(nc);
+                    match nc {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            '&' if !in_single && !in_double && chars.peek() == Some(&'&') => {
+                chars.next();
+                segments.push(std::mem::take(&mut current));
+            }
+            '|' if !in_single && !in_double => {
+                if chars.peek() == Some(&'|') {
+                    chars.next();
+                }
+                segments.push(std::mem::take(&mut current));
+            }
+            ';' if !in_single && !in_double => {
+                segments.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    segments.push(current);
+
+    segments
+        .into_iter()
+        .map(|segment| segment.trim().to_string())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Returns the binary a segment actually runs, skipping leading `VAR=val`
+/// assignments, `sudo`/`env` wrappers, and redirections like `2>&1`
+pub fn leading_binary(segment: &str) -> Option<&str> {
+    segment
+        .split_whitespace()
+        .find(|token| !is_assignment(token) && !is_wrapper(token) && !is_redirection(token))
+}
+
+fn is_assignment(token: &str) -> bool {
+    match token.split_once('=') {
+        Some((name, _)) => !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'),
+        None => false,
+    }
+}
+
+fn is_wrapper(token: &str) -> bool {
+    matches!(token, "sudo" | "env")
+}
+
+fn is_redirection(token: &str) -> bool {
+    let token = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    token.starts_with('>') || token.starts_with('<')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(lines: &[&str]) -> Vec<Entry> {
+        lines.iter().map(|line| (line.to_string(), None)).collect()
+    }
+
+    fn lines(joined: Vec<Entry>) -> Vec<String> {
+        joined.into_iter().map(|(line, _)| line).collect()
+    }
+
+    #[test]
+    fn joins_backslash_continuations() {
+        let joined = join_continuations(entries(&["echo foo \\", "bar"]));
+
+        assert_eq!(lines(joined), vec!["echo foo bar"]);
+    }
+
+    #[test]
+    fn joins_heredoc_body_until_delimiter() {
+        let joined = join_continuations(entries(&["cat <<EOF", "one", "two", "EOF", "pwd"]));
+
+        assert_eq!(lines(joined), vec!["cat <<EOF\none\ntwo", "pwd"]);
+    }
+
+    #[test]
+    fn does_not_mistake_ordinary_text_for_a_heredoc() {
+        let joined = join_continuations(entries(&[
+            "echo hello",
+            "some text about <<here state machines>>",
+            "ls -la",
+            "pwd",
+            "echo after",
+        ]));
+
+        assert_eq!(
+            lines(joined),
+            vec![
+                "echo hello",
+                "some text about <<here state machines>>",
+                "ls -la",
+                "pwd",
+                "echo after",
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_mistake_a_quoted_heredoc_marker_for_an_operator() {
+        let joined = join_continuations(entries(&["echo '<<EOF'", "ls"]));
+
+        assert_eq!(lines(joined), vec!["echo '<<EOF'", "ls"]);
+    }
+
+    #[test]
+    fn segments_splits_on_pipe_and_and_and_semicolon() {
+        assert_eq!(
+            segments("cat x | grep y && sort; echo done"),
+            vec!["cat x", "grep y", "sort", "echo done"]
+        );
+    }
+
+    #[test]
+    fn segments_ignores_operators_inside_quotes_and_substitutions() {
+        assert_eq!(
+            segments("echo 'a | b' && echo \"c && d\" && echo $(a | b)"),
+            vec!["echo 'a | b'", "echo \"c && d\"", "echo $(a | b)"]
+        );
+    }
+
+    #[test]
+    fn leading_binary_skips_assignments_wrappers_and_redirections() {
+        assert_eq!(
+            leading_binary("FOO=bar sudo env 2>&1 git push"),
+            Some("git")
+        );
+    }
+
+    #[test]
+    fn leading_binary_returns_none_for_an_empty_segment() {
+        assert_eq!(leading_binary("   "), None);
+    }
+}